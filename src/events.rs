@@ -0,0 +1,30 @@
+extern crate glfw;
+
+/// Application-level events, decoupled from `glfw::WindowEvent` so the
+/// render loop doesn't need to depend on GLFW's event enum directly.
+#[derive(Debug, Clone, Copy)]
+pub enum Act {
+    MouseMove { x: f64, y: f64 },
+    Resize { w: i32, h: i32 },
+    Close,
+}
+
+/// Translates a raw `glfw::WindowEvent` into an `Act`, or `None` for
+/// events this application doesn't act on.
+///
+/// Note: GLFW requires `glfw.poll_events()` to run on the main/context
+/// thread, and on some platforms that call itself enters a blocking modal
+/// loop during an OS-driven window drag/resize — no event reaches this
+/// function (or anywhere else) until it returns. Actually decoupling
+/// rendering from that would mean driving redraws from GLFW's window
+/// refresh callback instead of the main loop; a background thread can't
+/// help here since the events aren't available to forward until
+/// `poll_events()` unblocks.
+pub fn translate(event: glfw::WindowEvent) -> Option<Act> {
+    match event {
+        glfw::WindowEvent::CursorPos(x, y) => Some(Act::MouseMove { x, y }),
+        glfw::WindowEvent::FramebufferSize(w, h) => Some(Act::Resize { w, h }),
+        glfw::WindowEvent::Close => Some(Act::Close),
+        _ => None,
+    }
+}
@@ -0,0 +1,43 @@
+extern crate gl;
+use self::gl::types::*;
+
+/// Maps a `glGetError()` code to its enum name, for diagnostics.
+pub fn opengl_errno_name(code: GLenum) -> &'static str {
+    match code {
+        gl::NO_ERROR => "NO_ERROR",
+        gl::INVALID_ENUM => "INVALID_ENUM",
+        gl::INVALID_VALUE => "INVALID_VALUE",
+        gl::INVALID_OPERATION => "INVALID_OPERATION",
+        gl::STACK_OVERFLOW => "STACK_OVERFLOW",
+        gl::STACK_UNDERFLOW => "STACK_UNDERFLOW",
+        gl::OUT_OF_MEMORY => "OUT_OF_MEMORY",
+        _ => "UNKNOWN_ERROR",
+    }
+}
+
+/// Evaluates the wrapped GL call, then drains `glGetError()` until
+/// `GL_NO_ERROR`, printing `file:line: <expr> failed: <NAME>` for each
+/// error encountered. In debug builds a reported error aborts the process
+/// immediately, since a corrupted GL error state almost always means the
+/// following frames are garbage too; release builds only print.
+macro_rules! check_gl {
+    ($expr:expr) => {{
+        let result = $expr;
+        loop {
+            let error = gl::GetError();
+            if error == gl::NO_ERROR {
+                break;
+            }
+            eprintln!(
+                "{}:{}: {} failed: {}",
+                file!(),
+                line!(),
+                stringify!($expr),
+                crate::gl_check::opengl_errno_name(error)
+            );
+            #[cfg(debug_assertions)]
+            std::process::abort();
+        }
+        result
+    }};
+}
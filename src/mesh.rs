@@ -0,0 +1,102 @@
+extern crate gl;
+use self::gl::types::*;
+
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+
+/// A VAO/VBO/EBO triple for indexed geometry, drawn with `glDrawElements`.
+///
+/// The EBO must be bound via `gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo)`
+/// while the VAO is bound, and the VAO must not be unbound before the EBO
+/// binding happens: `GL_ELEMENT_ARRAY_BUFFER` bindings are stored as part of
+/// the VAO's state, not globally, so binding the EBO outside of the VAO's
+/// bind/unbind pair (or unbinding it early) leaves the VAO without an index
+/// buffer and `glDrawElements` silently draws nothing.
+pub struct Mesh {
+    vao: GLuint,
+    #[allow(dead_code)]
+    vbo: GLuint,
+    #[allow(dead_code)]
+    ebo: GLuint,
+    index_count: GLsizei,
+}
+
+impl Mesh {
+    /// Uploads `vertices` and `indices` into a freshly generated
+    /// VAO/VBO/EBO set. `attrib_sizes` describes the interleaved per-vertex
+    /// layout as the component count of each attribute, e.g. `&[3]` for a
+    /// plain `vec3` position, or `&[3, 3]` for a `vec3` position followed
+    /// by a `vec3` color, packed as `[x, y, z, r, g, b, ...]`. Attributes
+    /// are bound to locations `0, 1, ...` in order.
+    pub fn new(vertices: &[f32], indices: &[u32], attrib_sizes: &[GLint]) -> Mesh {
+        unsafe {
+            let (mut vao, mut vbo, mut ebo) = (0, 0, 0);
+            check_gl!(gl::GenVertexArrays(1, &mut vao));
+            check_gl!(gl::GenBuffers(1, &mut vbo));
+            check_gl!(gl::GenBuffers(1, &mut ebo));
+
+            // bind the VAO first: the VBO/EBO bindings and attribute setup
+            // below are all recorded into this VAO's state.
+            gl::BindVertexArray(vao);
+
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            check_gl!(gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (vertices.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                vertices.as_ptr() as *const c_void,
+                gl::STATIC_DRAW,
+            ));
+
+            // the EBO binding must happen while the VAO is bound (see the
+            // invariant documented on `Mesh`), and it must not be unbound
+            // before the VAO is.
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo);
+            check_gl!(gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                (indices.len() * mem::size_of::<u32>()) as GLsizeiptr,
+                indices.as_ptr() as *const c_void,
+                gl::STATIC_DRAW,
+            ));
+
+            let stride: GLint = attrib_sizes.iter().sum::<GLint>() * mem::size_of::<GLfloat>() as GLint;
+            let mut offset: usize = 0;
+            for (location, &size) in attrib_sizes.iter().enumerate() {
+                check_gl!(gl::VertexAttribPointer(
+                    location as GLuint,
+                    size,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    stride,
+                    (offset * mem::size_of::<GLfloat>()) as *const c_void,
+                ));
+                gl::EnableVertexAttribArray(location as GLuint);
+                offset += size as usize;
+            }
+
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            // do NOT unbind GL_ELEMENT_ARRAY_BUFFER here: doing so before
+            // unbinding the VAO clears the VAO's index buffer binding.
+            gl::BindVertexArray(0);
+
+            Mesh {
+                vao,
+                vbo,
+                ebo,
+                index_count: indices.len() as GLsizei,
+            }
+        }
+    }
+
+    pub fn draw(&self) {
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            check_gl!(gl::DrawElements(
+                gl::TRIANGLES,
+                self.index_count,
+                gl::UNSIGNED_INT,
+                ptr::null(),
+            ));
+        }
+    }
+}
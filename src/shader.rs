@@ -0,0 +1,135 @@
+extern crate gl;
+use self::gl::types::*;
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fs;
+use std::ptr;
+use std::str;
+
+/// A compiled and linked GLSL shader program, loaded from a pair of source
+/// files on disk.
+///
+/// Uniform locations are queried once (on first use) and cached, so the
+/// render loop can call the `set_*` setters every frame without re-running
+/// `glGetUniformLocation`.
+pub struct Shader {
+    id: GLuint,
+    uniform_locations: HashMap<String, GLint>,
+}
+
+impl Shader {
+    /// Reads `vertex_path` and `fragment_path`, compiles each stage and
+    /// links them into a program. Compile/link failures are returned as
+    /// `Err` with the driver's info log instead of being printed.
+    pub fn from_files(vertex_path: &str, fragment_path: &str) -> Result<Shader, String> {
+        let vertex_source = fs::read_to_string(vertex_path)
+            .map_err(|e| format!("failed to read {}: {}", vertex_path, e))?;
+        let fragment_source = fs::read_to_string(fragment_path)
+            .map_err(|e| format!("failed to read {}: {}", fragment_path, e))?;
+
+        unsafe {
+            let vertex_shader = compile_shader(&vertex_source, gl::VERTEX_SHADER, "vertex")?;
+            let fragment_shader =
+                compile_shader(&fragment_source, gl::FRAGMENT_SHADER, "fragment")?;
+
+            let id = gl::CreateProgram();
+            gl::AttachShader(id, vertex_shader);
+            gl::AttachShader(id, fragment_shader);
+            gl::LinkProgram(id);
+            link_error(id)?;
+
+            gl::DeleteShader(vertex_shader);
+            gl::DeleteShader(fragment_shader);
+
+            Ok(Shader {
+                id,
+                uniform_locations: HashMap::new(),
+            })
+        }
+    }
+
+    /// Activates this program (`glUseProgram`). Must be called before any
+    /// of the `set_*` uniform setters.
+    pub fn use_program(&self) {
+        unsafe { gl::UseProgram(self.id) }
+    }
+
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        unsafe { gl::Uniform1f(self.uniform_location(name), value) }
+    }
+
+    pub fn set_int(&mut self, name: &str, value: i32) {
+        unsafe { gl::Uniform1i(self.uniform_location(name), value) }
+    }
+
+    pub fn set_vec4(&mut self, name: &str, x: f32, y: f32, z: f32, w: f32) {
+        unsafe { gl::Uniform4f(self.uniform_location(name), x, y, z, w) }
+    }
+
+    /// `mat` is expected in column-major order, as OpenGL wants it.
+    pub fn set_mat4(&mut self, name: &str, mat: &[f32; 16]) {
+        unsafe {
+            gl::UniformMatrix4fv(self.uniform_location(name), 1, gl::FALSE, mat.as_ptr());
+        }
+    }
+
+    /// Looks up `name` in the cache, falling back to `glGetUniformLocation`
+    /// on a miss and caching the result for next time.
+    fn uniform_location(&mut self, name: &str) -> GLint {
+        if let Some(&location) = self.uniform_locations.get(name) {
+            return location;
+        }
+        let c_name = CString::new(name).unwrap();
+        let location = unsafe { gl::GetUniformLocation(self.id, c_name.as_ptr()) };
+        self.uniform_locations.insert(name.to_string(), location);
+        location
+    }
+}
+
+unsafe fn compile_shader(source: &str, kind: GLenum, label: &str) -> Result<GLuint, String> {
+    let shader = gl::CreateShader(kind);
+    let c_str = CString::new(source.as_bytes()).unwrap();
+    gl::ShaderSource(shader, 1, &c_str.as_ptr(), ptr::null());
+    gl::CompileShader(shader);
+
+    let mut success = gl::FALSE as GLint;
+    gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut success);
+    if success != gl::TRUE as GLint {
+        return Err(format!("{} shader compile failed\n{}", label, shader_info_log(shader)));
+    }
+    Ok(shader)
+}
+
+unsafe fn link_error(program: GLuint) -> Result<(), String> {
+    let mut success = gl::FALSE as GLint;
+    gl::GetProgramiv(program, gl::LINK_STATUS, &mut success);
+    if success != gl::TRUE as GLint {
+        return Err(format!("linking failed\n{}", program_info_log(program)));
+    }
+    Ok(())
+}
+
+unsafe fn shader_info_log(shader: GLuint) -> String {
+    let mut info_log = vec![0u8; 512];
+    let mut length: GLsizei = 0;
+    gl::GetShaderInfoLog(
+        shader,
+        512,
+        &mut length,
+        info_log.as_mut_ptr() as *mut GLchar,
+    );
+    str::from_utf8(&info_log[..length as usize]).unwrap().to_string()
+}
+
+unsafe fn program_info_log(program: GLuint) -> String {
+    let mut info_log = vec![0u8; 512];
+    let mut length: GLsizei = 0;
+    gl::GetProgramInfoLog(
+        program,
+        512,
+        &mut length,
+        info_log.as_mut_ptr() as *mut GLchar,
+    );
+    str::from_utf8(&info_log[..length as usize]).unwrap().to_string()
+}